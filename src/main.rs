@@ -5,8 +5,8 @@ extern crate boolfuck;
 use boolfuck::*;
 
 fn main() {
-    let code = "";
+    let code = "+";
     let input = vec![];
-    let mut state = State::new(code.chars().collect(), input);
-    while state.step() {}
+    let mut state = State::new(code.chars().collect(), input).expect("invalid program");
+    state.run().expect("interpreter error");
 }